@@ -1,44 +1,104 @@
+use crate::config::AppConfig;
+use crate::sync_queue::{self, PendingRequest};
+use futures_util::StreamExt;
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use reqwest::Client;
 use serde_json::Value;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use std::time::Duration;
+use tauri::{AppHandle, Manager};
+use tokio::sync::Mutex;
 
+/// Shared, app-managed client so every command and background task sees the same
+/// base URL and auth token, and settings changes take effect without a restart.
+pub type SharedApiClient = Arc<Mutex<ApiClient>>;
+
+/// Set from the tray's "Pause capture" toggle; checked by `capture_idea` so a single
+/// process-wide flag gates captures regardless of which client instance handles them.
+static CAPTURE_PAUSED: AtomicBool = AtomicBool::new(false);
+
+pub fn set_capture_paused(paused: bool) {
+    CAPTURE_PAUSED.store(paused, Ordering::Relaxed);
+}
+
+pub fn is_capture_paused() -> bool {
+    CAPTURE_PAUSED.load(Ordering::Relaxed)
+}
+
+/// Parses one SSE frame's `data:` lines into `(event name, payload)` pairs, reading the
+/// event name from the payload's `type` field and falling back to `notification`. Lines
+/// that aren't valid JSON are logged and skipped.
+fn parse_event_frame(frame: &str) -> Vec<(String, Value)> {
+    frame
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .filter_map(|data| match serde_json::from_str::<Value>(data.trim()) {
+            Ok(payload) => {
+                let event_name = payload
+                    .get("type")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("notification")
+                    .to_string();
+                Some((event_name, payload))
+            }
+            Err(e) => {
+                log::warn!("Failed to parse event stream frame: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
+#[derive(Clone)]
 pub struct ApiClient {
     client: Client,
     base_url: String,
 }
 
 impl ApiClient {
+    /// Builds a client from the persisted settings (backend URL, token, timeout).
     pub fn new() -> Self {
-        let client = Client::builder()
-            .timeout(Duration::from_secs(30))
-            .build()
-            .expect("Failed to create HTTP client");
+        Self::from_config(&AppConfig::load())
+    }
 
-        Self {
-            client,
-            base_url: "http://localhost:8000".to_string(), // Default Aether backend URL
+    pub fn from_config(config: &AppConfig) -> Self {
+        let mut headers = HeaderMap::new();
+        if let Some(token) = &config.api_token {
+            if let Ok(value) = HeaderValue::from_str(&format!("Bearer {}", token)) {
+                headers.insert(AUTHORIZATION, value);
+            }
         }
-    }
 
-    pub fn with_base_url(base_url: String) -> Self {
         let client = Client::builder()
-            .timeout(Duration::from_secs(30))
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .default_headers(headers)
             .build()
             .expect("Failed to create HTTP client");
 
-        Self { client, base_url }
+        Self {
+            client,
+            base_url: config.base_url.clone(),
+        }
     }
 
     pub async fn capture_idea(&self, idea: &str) -> Result<String, Box<dyn std::error::Error>> {
+        if is_capture_paused() {
+            log::info!("Capture is paused via tray toggle, dropping idea");
+            return Ok("Capture is paused".to_string());
+        }
+
         log::info!("Sending idea to backend: {}", idea);
 
-        let url = format!("{}/api/v1/ideas", self.base_url);
+        let endpoint = "/api/v1/ideas";
         let payload = serde_json::json!({
             "content": idea,
             "source": "desktop_app",
             "timestamp": chrono::Utc::now().to_rfc3339()
         });
 
+        let url = format!("{}{}", self.base_url, endpoint);
+
         match self.client.post(&url).json(&payload).send().await {
             Ok(response) => {
                 if response.status().is_success() {
@@ -53,20 +113,13 @@ impl ApiClient {
                         }
                     }
                 } else {
-                    let error_msg = format!("API request failed with status: {}", response.status());
-                    log::error!("{}", error_msg);
-                    
-                    // For now, return success even if API is down (offline mode)
-                    log::info!("API unavailable, storing idea locally");
-                    self.store_idea_locally(idea).await
+                    log::warn!("API request failed with status: {}", response.status());
+                    self.enqueue_for_sync(endpoint, "POST", &payload).await
                 }
             }
             Err(e) => {
-                log::error!("Failed to send request: {}", e);
-                
-                // Fallback to local storage
-                log::info!("Backend unavailable, storing idea locally");
-                self.store_idea_locally(idea).await
+                log::warn!("Backend unreachable: {}", e);
+                self.enqueue_for_sync(endpoint, "POST", &payload).await
             }
         }
     }
@@ -134,31 +187,172 @@ impl ApiClient {
         }
     }
 
-    async fn store_idea_locally(&self, idea: &str) -> Result<String, Box<dyn std::error::Error>> {
-        // Store idea in local file system for offline mode
-        use std::fs::OpenOptions;
-        use std::io::Write;
-        
-        let app_data_dir = dirs::data_dir()
-            .unwrap_or_else(|| std::path::PathBuf::from("."))
-            .join("aether");
-        
-        // Create directory if it doesn't exist
-        std::fs::create_dir_all(&app_data_dir)?;
-        
-        let ideas_file = app_data_dir.join("offline_ideas.txt");
-        let timestamp = chrono::Utc::now().to_rfc3339();
-        let entry = format!("[{}] {}\n", timestamp, idea);
-        
-        let mut file = OpenOptions::new()
-            .create(true)
-            .append(true)
-            .open(ideas_file)?;
-        
-        file.write_all(entry.as_bytes())?;
-        
-        log::info!("Idea stored locally for offline sync");
-        Ok("Idea stored locally (offline mode)".to_string())
+    /// Queues a mutation that couldn't reach the backend so it can be replayed later,
+    /// and reports honestly that it was queued rather than sent live.
+    async fn enqueue_for_sync(
+        &self,
+        endpoint: &str,
+        method: &str,
+        payload: &Value,
+    ) -> Result<String, Box<dyn std::error::Error>> {
+        sync_queue::queue().await.enqueue(endpoint, method, payload).await?;
+        log::info!("Queued {} {} for background sync", method, endpoint);
+        Ok("Queued for sync (backend unavailable)".to_string())
+    }
+
+    /// Resends every due entry in the offline queue, marking successes synced and
+    /// leaving failures queued for the next backoff window.
+    async fn drain_pending_sync(&self) {
+        let queue = sync_queue::queue().await;
+
+        let due = match queue.due_entries().await {
+            Ok(entries) => entries,
+            Err(e) => {
+                log::error!("Failed to read pending sync queue: {}", e);
+                return;
+            }
+        };
+
+        for entry in due {
+            self.resend_pending(queue, &entry).await;
+        }
+    }
+
+    async fn resend_pending(&self, queue: &sync_queue::SyncQueue, entry: &PendingRequest) {
+        let url = format!("{}{}", self.base_url, entry.endpoint);
+        let payload: Value = match serde_json::from_str(&entry.payload_json) {
+            Ok(value) => value,
+            Err(e) => {
+                log::error!("Dropping malformed queued entry {}: {}", entry.id, e);
+                let _ = queue.mark_synced(entry.id).await;
+                return;
+            }
+        };
+
+        let request = match entry.method.as_str() {
+            "POST" => self.client.post(&url).json(&payload),
+            "PUT" => self.client.put(&url).json(&payload),
+            "PATCH" => self.client.patch(&url).json(&payload),
+            "DELETE" => self.client.delete(&url).json(&payload),
+            other => {
+                log::error!("Dropping queued entry {} with unsupported method: {}", entry.id, other);
+                let _ = queue.mark_synced(entry.id).await;
+                return;
+            }
+        };
+
+        match request.send().await {
+            Ok(response) if response.status().is_success() => {
+                log::info!("Synced queued {} {} (id {})", entry.method, entry.endpoint, entry.id);
+                let _ = queue.mark_synced(entry.id).await;
+            }
+            Ok(response) => {
+                log::warn!(
+                    "Retry of queued entry {} failed with status {}, will retry later",
+                    entry.id,
+                    response.status()
+                );
+                let _ = queue.record_attempt(entry.id).await;
+            }
+            Err(e) => {
+                log::warn!("Retry of queued entry {} failed: {}, will retry later", entry.id, e);
+                let _ = queue.record_attempt(entry.id).await;
+            }
+        }
+    }
+
+    pub async fn get_pending_sync_count(&self) -> Result<i64, Box<dyn std::error::Error>> {
+        sync_queue::queue().await.pending_count().await
+    }
+
+    /// Drains the queue immediately, for the dashboard's "sync now" action.
+    pub async fn force_sync(&self) {
+        self.drain_pending_sync().await;
+    }
+
+    /// Runs forever as a background task: periodically drains the offline queue,
+    /// and also drains immediately whenever a `"backend-online"` event fires.
+    pub async fn run_sync_loop(app_handle: AppHandle, state: SharedApiClient) {
+        const DRAIN_INTERVAL: Duration = Duration::from_secs(30);
+
+        let signal_state = state.clone();
+        app_handle.listen_global("backend-online", move |_| {
+            log::info!("Received backend-online signal, draining sync queue");
+            let signal_state = signal_state.clone();
+            tokio::spawn(async move {
+                let client = signal_state.lock().await.clone();
+                client.drain_pending_sync().await;
+            });
+        });
+
+        let mut interval = tokio::time::interval(DRAIN_INTERVAL);
+        loop {
+            interval.tick().await;
+            let client = state.lock().await.clone();
+            client.drain_pending_sync().await;
+        }
+    }
+
+    /// Runs forever as a background task: holds open an SSE connection to the backend
+    /// and pushes each event straight to the webview, reconnecting with backoff on drop.
+    pub async fn run_event_stream(app_handle: AppHandle, state: SharedApiClient) {
+        const MIN_BACKOFF: Duration = Duration::from_secs(1);
+        const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+        let mut backoff = MIN_BACKOFF;
+
+        loop {
+            let client = state.lock().await.clone();
+            match client.connect_event_stream(&app_handle).await {
+                Ok(()) => {
+                    log::warn!("Backend event stream closed, reconnecting");
+                    backoff = MIN_BACKOFF;
+                }
+                Err(e) => {
+                    log::warn!("Backend event stream error: {}, reconnecting in {:?}", e, backoff);
+                }
+            }
+
+            let _ = app_handle.emit_all("connection-status", "offline");
+            tokio::time::sleep(backoff).await;
+            backoff = (backoff * 2).min(MAX_BACKOFF);
+        }
+    }
+
+    /// Opens the SSE stream and dispatches frames until the connection drops or errors.
+    async fn connect_event_stream(&self, app_handle: &AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+        let url = format!("{}/api/v1/stream", self.base_url);
+        let response = self.client.get(&url).send().await?;
+
+        if !response.status().is_success() {
+            return Err(format!("Event stream endpoint returned {}", response.status()).into());
+        }
+
+        let _ = app_handle.emit_all("connection-status", "online");
+        let _ = app_handle.emit_all("backend-online", ());
+        log::info!("Connected to backend event stream");
+
+        let mut stream = response.bytes_stream();
+        let mut buffer = String::new();
+
+        while let Some(chunk) = stream.next().await {
+            buffer.push_str(&String::from_utf8_lossy(&chunk?));
+
+            while let Some(frame_end) = buffer.find("\n\n") {
+                let frame: String = buffer.drain(..frame_end + 2).collect();
+                Self::dispatch_event_frame(app_handle, &frame);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Parses one SSE frame's `data:` lines as JSON and emits each under its `type` field
+    /// (`notification`, `task_updated`, `idea_processed`, ...), falling back to `notification`.
+    fn dispatch_event_frame(app_handle: &AppHandle, frame: &str) {
+        for (event_name, payload) in parse_event_frame(frame) {
+            let _ = app_handle.emit_all(&event_name, payload);
+        }
     }
 
     fn get_mock_dashboard_data(&self) -> Value {
@@ -207,4 +401,40 @@ impl ApiClient {
             ]
         })
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_typed_event() {
+        let frame = "event: message\ndata: {\"type\":\"task_updated\",\"id\":1}\n\n";
+        let events = parse_event_frame(frame);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].0, "task_updated");
+        assert_eq!(events[0].1["id"], 1);
+    }
+
+    #[test]
+    fn falls_back_to_notification_without_type() {
+        let frame = "data: {\"message\":\"hello\"}\n\n";
+        let events = parse_event_frame(frame);
+        assert_eq!(events[0].0, "notification");
+    }
+
+    #[test]
+    fn skips_malformed_json() {
+        let frame = "data: not json\n\n";
+        assert!(parse_event_frame(frame).is_empty());
+    }
+
+    #[test]
+    fn handles_multiple_data_lines_in_one_frame() {
+        let frame = "data: {\"type\":\"a\"}\ndata: {\"type\":\"b\"}\n\n";
+        let events = parse_event_frame(frame);
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].0, "a");
+        assert_eq!(events[1].0, "b");
+    }
 }
\ No newline at end of file
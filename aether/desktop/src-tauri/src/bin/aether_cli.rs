@@ -0,0 +1,86 @@
+// Companion CLI for driving a running Aether desktop instance without opening the UI,
+// e.g. `aether capture "refactor the parser"` or `aether show`.
+
+use serde_json::Value;
+use std::env;
+use std::io::{BufRead, BufReader, Write};
+use std::net::TcpStream;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn port_file_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("aether")
+        .join("ipc_port")
+}
+
+fn read_port() -> Result<u16, String> {
+    let path = port_file_path();
+    let contents = std::fs::read_to_string(&path)
+        .map_err(|e| format!("Aether doesn't appear to be running ({}: {})", path.display(), e))?;
+    contents
+        .trim()
+        .parse::<u16>()
+        .map_err(|e| format!("Invalid IPC port file at {}: {}", path.display(), e))
+}
+
+fn send_command(action: &str, payload: Value) -> Result<Value, String> {
+    let port = read_port()?;
+    let mut stream = TcpStream::connect(("127.0.0.1", port))
+        .map_err(|e| format!("Failed to connect to running Aether instance: {}", e))?;
+
+    let mut command = serde_json::json!({ "action": action, "payload": payload }).to_string();
+    command.push('\n');
+    stream
+        .write_all(command.as_bytes())
+        .map_err(|e| format!("Failed to send command: {}", e))?;
+
+    let mut response_line = String::new();
+    BufReader::new(stream)
+        .read_line(&mut response_line)
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    serde_json::from_str::<Value>(response_line.trim())
+        .map_err(|e| format!("Malformed response from Aether: {}", e))
+}
+
+fn main() -> ExitCode {
+    env_logger::init();
+
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("capture") => {
+            let idea = args[1..].join(" ");
+            if idea.is_empty() {
+                eprintln!("Usage: aether capture <text>");
+                return ExitCode::FAILURE;
+            }
+            send_command("capture", Value::String(idea))
+        }
+        Some("show") => send_command("show", Value::Null),
+        _ => {
+            eprintln!("Usage: aether <capture \"text\" | show>");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match result {
+        Ok(response) if response.get("ok").and_then(Value::as_bool).unwrap_or(false) => {
+            if let Some(message) = response.get("message").and_then(Value::as_str) {
+                println!("{}", message);
+            }
+            ExitCode::SUCCESS
+        }
+        Ok(response) => {
+            let error = response.get("error").and_then(Value::as_str).unwrap_or("Unknown error");
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
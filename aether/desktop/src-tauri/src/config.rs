@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+fn default_base_url() -> String {
+    "http://localhost:8000".to_string()
+}
+
+fn default_timeout_secs() -> u64 {
+    30
+}
+
+/// Persisted app settings: where the Aether backend lives and how to authenticate with it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppConfig {
+    #[serde(default = "default_base_url")]
+    pub base_url: String,
+    #[serde(default)]
+    pub api_token: Option<String>,
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for AppConfig {
+    fn default() -> Self {
+        Self {
+            base_url: default_base_url(),
+            api_token: None,
+            timeout_secs: default_timeout_secs(),
+        }
+    }
+}
+
+impl AppConfig {
+    fn config_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("aether")
+            .join("config.json")
+    }
+
+    pub fn load() -> Self {
+        let path = Self::config_path();
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::config_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
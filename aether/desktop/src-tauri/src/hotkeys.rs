@@ -1,10 +1,130 @@
-use tauri::{AppHandle, Manager};
+use tauri::AppHandle;
 use global_hotkey::{GlobalHotKeyManager, HotKeyState, GlobalHotKeyEvent};
+use global_hotkey::hotkey::{HotKey, Modifiers, Code};
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+pub type SharedHotkeyManager = Arc<Mutex<HotkeyManager>>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hotkey {
+    pub keys: String,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotkeysConfig {
+    pub quick_capture: Hotkey,
+    pub show_window: Hotkey,
+}
+
+impl Default for HotkeysConfig {
+    fn default() -> Self {
+        Self {
+            quick_capture: Hotkey {
+                keys: "Ctrl+Shift+Space".to_string(),
+                enabled: true,
+            },
+            show_window: Hotkey {
+                keys: "Ctrl+Shift+KeyA".to_string(),
+                enabled: true,
+            },
+        }
+    }
+}
+
+impl HotkeysConfig {
+    fn config_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("aether")
+            .join("hotkeys.json")
+    }
+
+    fn load() -> Self {
+        let path = Self::config_path();
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::config_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    fn get(&self, action: &str) -> Option<Hotkey> {
+        match action {
+            "quick_capture" => Some(self.quick_capture.clone()),
+            "show_window" => Some(self.show_window.clone()),
+            _ => None,
+        }
+    }
+
+    fn set(&mut self, action: &str, hotkey: Hotkey) -> Result<(), String> {
+        match action {
+            "quick_capture" => self.quick_capture = hotkey,
+            "show_window" => self.show_window = hotkey,
+            _ => return Err(format!("Unknown hotkey action: {}", action)),
+        }
+        Ok(())
+    }
+}
+
+/// Normalizes a single key token to the W3C `Code` name `keyboard_types::Code::from_str`
+/// expects. Bare letters (`"A"`) and digits (`"1"`) become `KeyA`/`Digit1`; tokens that
+/// already look like a `Code` name (`"Space"`, `"F1"`) pass through unchanged.
+fn normalize_key_token(token: &str) -> String {
+    let mut chars = token.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) if c.is_ascii_alphabetic() => format!("Key{}", c.to_ascii_uppercase()),
+        (Some(c), None) if c.is_ascii_digit() => format!("Digit{}", c),
+        _ => token.to_string(),
+    }
+}
+
+/// Parses a combo string like "Ctrl+Shift+Space" into a `global_hotkey` `HotKey`.
+fn parse_hotkey(keys: &str) -> Result<HotKey, String> {
+    let mut modifiers = Modifiers::empty();
+    let mut code = None;
+
+    for part in keys.split('+') {
+        let part = part.trim();
+        match part.to_lowercase().as_str() {
+            "ctrl" | "control" => modifiers |= Modifiers::CONTROL,
+            "shift" => modifiers |= Modifiers::SHIFT,
+            "alt" | "option" => modifiers |= Modifiers::ALT,
+            "cmd" | "super" | "meta" | "win" => modifiers |= Modifiers::META,
+            "" => {}
+            _ => {
+                // Match the key itself in its original case: `Code::from_str` is case-sensitive.
+                let normalized = normalize_key_token(part);
+                code = Some(
+                    Code::from_str(&normalized)
+                        .map_err(|_| format!("Unrecognized key in '{}': {}", keys, part))?,
+                );
+            }
+        }
+    }
+
+    let code = code.ok_or_else(|| format!("No key found in hotkey string: {}", keys))?;
+    Ok(HotKey::new(Some(modifiers), code))
+}
 
 pub struct HotkeyManager {
     manager: GlobalHotKeyManager,
     hotkeys: HashMap<u32, String>,
+    registered: HashMap<String, HotKey>,
+    config: HotkeysConfig,
 }
 
 impl HotkeyManager {
@@ -12,62 +132,127 @@ impl HotkeyManager {
         Self {
             manager: GlobalHotKeyManager::new().expect("Failed to create hotkey manager"),
             hotkeys: HashMap::new(),
+            registered: HashMap::new(),
+            config: HotkeysConfig::load(),
         }
     }
 
-    pub async fn setup_hotkeys(&mut self, app_handle: AppHandle) -> Result<(), Box<dyn std::error::Error>> {
+    /// Registers every enabled hotkey from the persisted config and starts the event listener.
+    pub async fn setup(state: SharedHotkeyManager, app_handle: AppHandle) -> Result<(), Box<dyn std::error::Error>> {
         log::info!("Setting up global hotkeys");
 
-        // Register default hotkeys
-        self.register_quick_capture_hotkey()?;
-        self.register_show_window_hotkey()?;
+        {
+            let mut manager = state.lock().await;
+            manager.register_action("quick_capture")?;
+            manager.register_action("show_window")?;
+        }
 
-        // Start hotkey event listener
+        let listener_state = state.clone();
         let app_handle_clone = app_handle.clone();
         tokio::spawn(async move {
-            Self::listen_for_hotkey_events(app_handle_clone).await;
+            Self::listen_for_hotkey_events(listener_state, app_handle_clone).await;
         });
 
         log::info!("Global hotkeys setup completed");
         Ok(())
     }
 
-    fn register_quick_capture_hotkey(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        use global_hotkey::{hotkey::{HotKey, Modifiers, Code}};
+    fn register_action(&mut self, action: &str) -> Result<(), Box<dyn std::error::Error>> {
+        let hotkey_cfg = self
+            .config
+            .get(action)
+            .ok_or_else(|| format!("Unknown hotkey action: {}", action))?;
 
-        // Ctrl+Shift+Space for quick capture
-        let hotkey = HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::Space);
+        if !hotkey_cfg.enabled {
+            log::info!("Hotkey for '{}' is disabled, skipping registration", action);
+            return Ok(());
+        }
+
+        let hotkey = parse_hotkey(&hotkey_cfg.keys)?;
         let hotkey_id = self.manager.register(hotkey)?;
-        
-        self.hotkeys.insert(hotkey_id, "quick_capture".to_string());
-        log::info!("Registered quick capture hotkey: Ctrl+Shift+Space");
-        
+
+        self.hotkeys.insert(hotkey_id, action.to_string());
+        self.registered.insert(action.to_string(), hotkey);
+        log::info!("Registered '{}' hotkey: {}", action, hotkey_cfg.keys);
+
+        Ok(())
+    }
+
+    fn unregister_action(&mut self, action: &str) -> Result<(), Box<dyn std::error::Error>> {
+        if let Some(hotkey) = self.registered.remove(action) {
+            self.manager.unregister(hotkey)?;
+            self.hotkeys.retain(|_, a| a != action);
+            log::info!("Unregistered '{}' hotkey", action);
+        }
         Ok(())
     }
 
-    fn register_show_window_hotkey(&mut self) -> Result<(), Box<dyn std::error::Error>> {
-        use global_hotkey::{hotkey::{HotKey, Modifiers, Code}};
+    pub fn get_hotkeys(&self) -> HotkeysConfig {
+        self.config.clone()
+    }
+
+    /// Unregisters the action's current binding (if any), validates and registers the new one,
+    /// and persists the config. Returns an error if the combo is already taken or malformed.
+    pub fn set_hotkey(&mut self, action: &str, keys: String, enabled: bool) -> Result<(), String> {
+        if self.config.get(action).is_none() {
+            return Err(format!("Unknown hotkey action: {}", action));
+        }
+
+        parse_hotkey(&keys)?;
+
+        self.unregister_action(action)
+            .map_err(|e| format!("Failed to unregister existing hotkey: {}", e))?;
+
+        let previous = self.config.get(action);
+        self.config.set(action, Hotkey { keys: keys.clone(), enabled })?;
+
+        if enabled {
+            if let Err(e) = self.register_action(action) {
+                // Roll back so the in-memory config and the OS registration both match
+                // what was working before this attempt.
+                if let Some(previous) = previous {
+                    let _ = self.config.set(action, previous);
+                    let _ = self.register_action(action);
+                }
+                return Err(format!("Hotkey '{}' is already in use or invalid: {}", keys, e));
+            }
+        }
+
+        self.config
+            .save()
+            .map_err(|e| format!("Failed to save hotkey config: {}", e))?;
 
-        // Ctrl+Shift+A for show main window
-        let hotkey = HotKey::new(Some(Modifiers::CONTROL | Modifiers::SHIFT), Code::KeyA);
-        let hotkey_id = self.manager.register(hotkey)?;
-        
-        self.hotkeys.insert(hotkey_id, "show_window".to_string());
-        log::info!("Registered show window hotkey: Ctrl+Shift+A");
-        
         Ok(())
     }
 
-    async fn listen_for_hotkey_events(app_handle: AppHandle) {
-        use global_hotkey::GlobalHotKeyEvent;
-        
+    async fn listen_for_hotkey_events(state: SharedHotkeyManager, app_handle: AppHandle) {
         log::info!("Starting hotkey event listener");
-        
+
         if let Ok(receiver) = GlobalHotKeyEvent::receiver() {
             loop {
                 if let Ok(event) = receiver.recv() {
                     if event.state == HotKeyState::Pressed {
-                        Self::handle_hotkey_event(&app_handle, event.id).await;
+                        let action = {
+                            let manager = state.lock().await;
+                            manager.hotkeys.get(&event.id).cloned()
+                        };
+
+                        match action.as_deref() {
+                            Some("quick_capture") => {
+                                log::info!("Quick capture hotkey activated");
+                                Self::handle_quick_capture(&app_handle).await;
+                            }
+                            Some("show_window") => {
+                                log::info!("Show window hotkey activated");
+                                Self::handle_show_window(&app_handle).await;
+                            }
+                            Some(other) => {
+                                log::warn!("No handler for hotkey action: {}", other);
+                            }
+                            None => {
+                                log::warn!("Unknown hotkey ID: {}", event.id);
+                            }
+                        }
                     }
                 }
             }
@@ -76,38 +261,11 @@ impl HotkeyManager {
         }
     }
 
-    async fn handle_hotkey_event(app_handle: &AppHandle, hotkey_id: u32) {
-        log::info!("Hotkey pressed: ID {}", hotkey_id);
-
-        // For now, we'll handle based on known IDs
-        // In a real implementation, we'd maintain the mapping
-        match hotkey_id {
-            _ if Self::is_quick_capture_hotkey(hotkey_id) => {
-                log::info!("Quick capture hotkey activated");
-                Self::handle_quick_capture(app_handle).await;
-            }
-            _ if Self::is_show_window_hotkey(hotkey_id) => {
-                log::info!("Show window hotkey activated");
-                Self::handle_show_window(app_handle).await;
-            }
-            _ => {
-                log::warn!("Unknown hotkey ID: {}", hotkey_id);
-            }
-        }
-    }
-
-    fn is_quick_capture_hotkey(hotkey_id: u32) -> bool {
-        // This is a simplified check - in practice you'd maintain the mapping
-        hotkey_id == 1 // Assuming first registered hotkey
-    }
-
-    fn is_show_window_hotkey(hotkey_id: u32) -> bool {
-        // This is a simplified check - in practice you'd maintain the mapping
-        hotkey_id == 2 // Assuming second registered hotkey
-    }
-
     async fn handle_quick_capture(app_handle: &AppHandle) {
+        use tauri::Manager;
+
         log::info!("Handling quick capture hotkey");
+        let _ = app_handle.emit_all("hotkey-activated", "quick_capture");
 
         // Show quick capture window
         if let Some(window) = app_handle.get_window("quick-capture") {
@@ -144,14 +302,40 @@ impl HotkeyManager {
     }
 
     async fn handle_show_window(app_handle: &AppHandle) {
+        use tauri::Manager;
+
         log::info!("Handling show window hotkey");
+        let _ = app_handle.emit_all("hotkey-activated", "show_window");
 
-        if let Some(window) = app_handle.get_window("main") {
-            let _ = window.show();
-            let _ = window.set_focus();
-            let _ = window.unminimize();
-            let _ = window.center();
-            log::info!("Main window shown via hotkey");
-        }
+        crate::tray::TrayManager::show_main_window(app_handle);
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_default_bindings() {
+        assert!(parse_hotkey("Ctrl+Shift+Space").is_ok());
+        assert!(parse_hotkey("Ctrl+Shift+KeyA").is_ok());
+    }
+
+    #[test]
+    fn normalizes_bare_letters_and_digits() {
+        // A bare letter/digit (what a user naturally types for "Ctrl+Shift+A") must
+        // resolve to the W3C code name the underlying library expects.
+        assert!(parse_hotkey("Ctrl+Shift+A").is_ok());
+        assert!(parse_hotkey("Ctrl+Alt+1").is_ok());
+    }
+
+    #[test]
+    fn rejects_unrecognized_key() {
+        assert!(parse_hotkey("Ctrl+Shift+NotAKey").is_err());
+    }
+
+    #[test]
+    fn rejects_combo_with_no_key() {
+        assert!(parse_hotkey("Ctrl+Shift").is_err());
+    }
+}
@@ -0,0 +1,42 @@
+use fluent_templates::{static_loader, LanguageIdentifier, Loader};
+use std::sync::{Mutex, OnceLock};
+
+static_loader! {
+    static LOCALES = {
+        locales: "./assets/locales",
+        fallback_language: "en-US",
+    };
+}
+
+static CURRENT_LOCALE: OnceLock<Mutex<LanguageIdentifier>> = OnceLock::new();
+
+fn fallback_locale() -> LanguageIdentifier {
+    "en-US".parse().expect("en-US is a valid language identifier")
+}
+
+/// Detects the OS locale via `sys-locale`, falling back to `en-US` if unavailable
+/// or unparseable.
+pub fn detect_locale() -> LanguageIdentifier {
+    sys_locale::get_locale()
+        .and_then(|locale| locale.parse().ok())
+        .unwrap_or_else(fallback_locale)
+}
+
+/// The locale tray titles and other UI strings currently resolve against.
+pub fn current_locale() -> LanguageIdentifier {
+    CURRENT_LOCALE
+        .get_or_init(|| Mutex::new(detect_locale()))
+        .lock()
+        .unwrap()
+        .clone()
+}
+
+/// Switches the active locale, used when the user changes their language in settings.
+pub fn set_current_locale(locale: LanguageIdentifier) {
+    *CURRENT_LOCALE.get_or_init(|| Mutex::new(detect_locale())).lock().unwrap() = locale;
+}
+
+/// Looks up `id` in `locale`'s Fluent bundle, falling back to `en-US` if missing.
+pub fn text(locale: &LanguageIdentifier, id: &str) -> String {
+    LOCALES.lookup(locale, id)
+}
@@ -0,0 +1,143 @@
+use crate::api::SharedApiClient;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::PathBuf;
+use tauri::{AppHandle, Manager};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+
+#[derive(Debug, Deserialize)]
+struct IpcCommand {
+    action: String,
+    #[serde(default)]
+    payload: Value,
+}
+
+#[derive(Debug, Serialize)]
+struct IpcResponse {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+impl IpcResponse {
+    fn ok(message: impl Into<String>) -> Self {
+        Self { ok: true, message: Some(message.into()), error: None }
+    }
+
+    fn err(error: impl Into<String>) -> Self {
+        Self { ok: false, message: None, error: Some(error.into()) }
+    }
+}
+
+fn port_file_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("aether")
+        .join("ipc_port")
+}
+
+/// Binds a loopback TCP listener for the companion CLI, records its port in the
+/// config dir, and dispatches incoming commands through the same code paths the
+/// frontend uses (`capture_idea`, `show_main_window`). Runs forever.
+pub async fn serve(app_handle: AppHandle, api_client: SharedApiClient) {
+    let listener = match TcpListener::bind("127.0.0.1:0").await {
+        Ok(listener) => listener,
+        Err(e) => {
+            log::error!("Failed to bind CLI IPC listener: {}", e);
+            return;
+        }
+    };
+
+    let port = match listener.local_addr() {
+        Ok(addr) => addr.port(),
+        Err(e) => {
+            log::error!("Failed to read CLI IPC listener address: {}", e);
+            return;
+        }
+    };
+
+    let path = port_file_path();
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            log::error!("Failed to create config dir for CLI IPC port file: {}", e);
+            return;
+        }
+    }
+    if let Err(e) = std::fs::write(&path, port.to_string()) {
+        log::error!("Failed to write CLI IPC port file: {}", e);
+        return;
+    }
+
+    log::info!("CLI IPC listener bound on 127.0.0.1:{}", port);
+
+    loop {
+        let (stream, _) = match listener.accept().await {
+            Ok(conn) => conn,
+            Err(e) => {
+                log::warn!("Failed to accept CLI IPC connection: {}", e);
+                continue;
+            }
+        };
+
+        let app_handle = app_handle.clone();
+        let api_client = api_client.clone();
+        tokio::spawn(async move {
+            handle_connection(stream, app_handle, api_client).await;
+        });
+    }
+}
+
+async fn handle_connection(stream: TcpStream, app_handle: AppHandle, api_client: SharedApiClient) {
+    let (reader, mut writer) = stream.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    let line = match lines.next_line().await {
+        Ok(Some(line)) => line,
+        Ok(None) => return,
+        Err(e) => {
+            log::warn!("Failed to read CLI IPC command: {}", e);
+            return;
+        }
+    };
+
+    let response = match serde_json::from_str::<IpcCommand>(&line) {
+        Ok(command) => dispatch(command, &app_handle, &api_client).await,
+        Err(e) => IpcResponse::err(format!("Malformed command: {}", e)),
+    };
+
+    let mut payload = serde_json::to_string(&response)
+        .unwrap_or_else(|_| "{\"ok\":false,\"error\":\"internal error\"}".to_string());
+    payload.push('\n');
+    let _ = writer.write_all(payload.as_bytes()).await;
+}
+
+async fn dispatch(command: IpcCommand, app_handle: &AppHandle, api_client: &SharedApiClient) -> IpcResponse {
+    match command.action.as_str() {
+        "capture" => {
+            let idea = match command.payload.as_str() {
+                Some(idea) => idea.to_string(),
+                None => return IpcResponse::err("Missing idea text in payload"),
+            };
+
+            let client = api_client.lock().await.clone();
+            match client.capture_idea(&idea).await {
+                Ok(message) => IpcResponse::ok(message),
+                Err(e) => IpcResponse::err(e.to_string()),
+            }
+        }
+        "show" => {
+            if let Some(window) = app_handle.get_window("main") {
+                let _ = window.show();
+                let _ = window.set_focus();
+                let _ = window.unminimize();
+                IpcResponse::ok("Main window shown")
+            } else {
+                IpcResponse::err("Main window not found")
+            }
+        }
+        other => IpcResponse::err(format!("Unknown action: {}", other)),
+    }
+}
@@ -1,10 +1,7 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::{
-    AppHandle, CustomMenuItem, Manager, SystemTray, SystemTrayEvent, SystemTrayMenu,
-    SystemTrayMenuItem, Window, WindowEvent,
-};
+use tauri::{AppHandle, Manager, SystemTray, SystemTrayEvent, Window, WindowEvent};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -12,11 +9,17 @@ mod tray;
 mod hotkeys;
 mod autostart;
 mod api;
+mod config;
+mod i18n;
+mod ipc;
+mod sync_queue;
+mod window_state;
 
-use tray::TrayManager;
-use hotkeys::HotkeyManager;
+use tray::{TrayManager, SharedTrayManager};
+use hotkeys::{HotkeyManager, HotkeysConfig, SharedHotkeyManager};
 use autostart::AutoStartManager;
-use api::ApiClient;
+use api::{ApiClient, SharedApiClient};
+use config::AppConfig;
 
 #[derive(Clone, serde::Serialize)]
 struct Payload {
@@ -26,11 +29,12 @@ struct Payload {
 
 // Tauri commands that can be called from the frontend
 #[tauri::command]
-async fn capture_idea(idea: String) -> Result<String, String> {
+async fn capture_idea(state: tauri::State<'_, SharedApiClient>, idea: String) -> Result<String, String> {
     log::info!("Capturing idea: {}", idea);
-    
+
     // Send idea to Aether backend
-    match ApiClient::new().capture_idea(&idea).await {
+    let client = state.lock().await.clone();
+    match client.capture_idea(&idea).await {
         Ok(response) => {
             log::info!("Idea captured successfully: {}", response);
             Ok(response)
@@ -43,10 +47,11 @@ async fn capture_idea(idea: String) -> Result<String, String> {
 }
 
 #[tauri::command]
-async fn get_dashboard_data() -> Result<serde_json::Value, String> {
+async fn get_dashboard_data(state: tauri::State<'_, SharedApiClient>) -> Result<serde_json::Value, String> {
     log::info!("Fetching dashboard data");
-    
-    match ApiClient::new().get_dashboard_data().await {
+
+    let client = state.lock().await.clone();
+    match client.get_dashboard_data().await {
         Ok(data) => Ok(data),
         Err(e) => {
             log::error!("Failed to fetch dashboard data: {}", e);
@@ -56,10 +61,11 @@ async fn get_dashboard_data() -> Result<serde_json::Value, String> {
 }
 
 #[tauri::command]
-async fn get_notifications() -> Result<serde_json::Value, String> {
+async fn get_notifications(state: tauri::State<'_, SharedApiClient>) -> Result<serde_json::Value, String> {
     log::info!("Fetching notifications");
-    
-    match ApiClient::new().get_notifications().await {
+
+    let client = state.lock().await.clone();
+    match client.get_notifications().await {
         Ok(data) => Ok(data),
         Err(e) => {
             log::error!("Failed to fetch notifications: {}", e);
@@ -137,34 +143,149 @@ async fn is_autostart_enabled() -> Result<bool, String> {
     }
 }
 
+#[tauri::command]
+async fn get_hotkeys(state: tauri::State<'_, SharedHotkeyManager>) -> Result<HotkeysConfig, String> {
+    let manager = state.lock().await;
+    Ok(manager.get_hotkeys())
+}
+
+#[tauri::command]
+async fn get_pending_sync_count(state: tauri::State<'_, SharedApiClient>) -> Result<i64, String> {
+    let client = state.lock().await.clone();
+    client
+        .get_pending_sync_count()
+        .await
+        .map_err(|e| format!("Failed to read pending sync count: {}", e))
+}
+
+#[tauri::command]
+async fn force_sync(state: tauri::State<'_, SharedApiClient>) -> Result<(), String> {
+    let client = state.lock().await.clone();
+    client.force_sync().await;
+    Ok(())
+}
+
+#[tauri::command]
+async fn get_settings() -> Result<AppConfig, String> {
+    Ok(AppConfig::load())
+}
+
+#[tauri::command]
+fn set_locale(app_handle: AppHandle, state: tauri::State<'_, SharedTrayManager>, locale: String) -> Result<(), String> {
+    let locale = locale
+        .parse()
+        .map_err(|e| format!("Invalid locale '{}': {:?}", locale, e))?;
+    TrayManager::rebuild_menu(&app_handle, state.inner(), locale);
+    Ok(())
+}
+
+#[tauri::command]
+async fn save_settings(state: tauri::State<'_, SharedApiClient>, config: AppConfig) -> Result<(), String> {
+    config
+        .save()
+        .map_err(|e| format!("Failed to save settings: {}", e))?;
+
+    // Re-create the shared client immediately so the new URL/token/timeout take effect
+    // without requiring an app restart.
+    let mut client = state.lock().await;
+    *client = ApiClient::from_config(&config);
+
+    Ok(())
+}
+
+#[tauri::command]
+async fn set_hotkey(
+    state: tauri::State<'_, SharedHotkeyManager>,
+    action: String,
+    keys: String,
+    enabled: bool,
+) -> Result<(), String> {
+    let mut manager = state.lock().await;
+    manager.set_hotkey(&action, keys, enabled)
+}
+
+/// Pulls the idea text out of a forwarded `--capture "text"` argv, if present.
+fn extract_capture_arg(argv: &[String]) -> Option<String> {
+    argv.iter()
+        .position(|arg| arg == "--capture")
+        .and_then(|i| argv.get(i + 1).cloned())
+}
+
+/// Pulls a navigation target out of a forwarded `--route <name>` argv or an
+/// `aether://<name>` deep link, if present.
+fn extract_route_arg(argv: &[String]) -> Option<String> {
+    if let Some(route) = argv
+        .iter()
+        .position(|arg| arg == "--route")
+        .and_then(|i| argv.get(i + 1).cloned())
+    {
+        return Some(route);
+    }
+
+    argv.iter().find_map(|arg| {
+        arg.strip_prefix("aether://")
+            .map(|route| route.trim_end_matches('/').to_string())
+    })
+}
+
 fn main() {
     // Initialize logging
     env_logger::init();
     
     log::info!("Starting Aether Desktop Application");
 
-    // Create system tray
-    let tray_menu = SystemTrayMenu::new()
-        .add_item(CustomMenuItem::new("show".to_string(), "Show Aether"))
-        .add_item(CustomMenuItem::new("capture".to_string(), "Quick Capture"))
-        .add_native_item(SystemTrayMenuItem::Separator)
-        .add_item(CustomMenuItem::new("dashboard".to_string(), "Dashboard"))
-        .add_item(CustomMenuItem::new("settings".to_string(), "Settings"))
-        .add_native_item(SystemTrayMenuItem::Separator)
-        .add_item(CustomMenuItem::new("quit".to_string(), "Quit"));
-
+    // Create system tray, with titles resolved against the detected OS locale
+    let tray_menu = TrayManager::initial_menu(&i18n::detect_locale());
     let system_tray = SystemTray::new().with_menu(tray_menu);
 
     tauri::Builder::default()
+        .plugin(tauri_plugin_single_instance::init(|app, argv, cwd| {
+            log::info!("Second instance launched with args {:?}, cwd: {}", argv, cwd);
+
+            let _ = app.emit_all("single-instance", Payload { args: argv.clone(), cwd });
+
+            TrayManager::show_main_window(app);
+
+            if let Some(idea) = extract_capture_arg(&argv) {
+                let client = app.state::<SharedApiClient>().inner().clone();
+                tokio::spawn(async move {
+                    let client = client.lock().await.clone();
+                    match client.capture_idea(&idea).await {
+                        Ok(response) => log::info!("Forwarded capture from second instance: {}", response),
+                        Err(e) => log::error!("Failed to forward capture from second instance: {}", e),
+                    }
+                });
+            }
+
+            if let Some(route) = extract_route_arg(&argv) {
+                log::info!("Routing second-instance launch to '{}'", route);
+                TrayManager::navigate(app, &route);
+            }
+        }))
         .system_tray(system_tray)
+        .plugin(tauri_plugin_positioner::init())
         .on_system_tray_event(|app, event| {
-            TrayManager::handle_tray_event(app, event);
+            let tray_manager = app.state::<SharedTrayManager>();
+            TrayManager::handle_tray_event(app, event, tray_manager.inner());
         })
         .setup(|app| {
-            // Initialize hotkey manager
+            // Share one configured API client across every command and background task
+            // so settings changes (base URL, token) take effect everywhere at once.
+            let api_client: SharedApiClient = Arc::new(Mutex::new(ApiClient::new()));
+            app.manage(api_client.clone());
+
+            // Tracks the tray's checkable toggle state.
+            let tray_manager: SharedTrayManager = Arc::new(std::sync::Mutex::new(TrayManager::new()));
+            TrayManager::apply_persisted_toggles(&app.handle(), &tray_manager);
+            app.manage(tray_manager);
+
+            // Initialize hotkey manager, keeping it alive as managed state so
+            // rebinding via `set_hotkey` can unregister/register at runtime.
+            let hotkey_manager: SharedHotkeyManager = Arc::new(Mutex::new(HotkeyManager::new()));
+            app.manage(hotkey_manager.clone());
             let app_handle = app.handle();
             tokio::spawn(async move {
-                if let Err(e) = HotkeyManager::new().setup_hotkeys(app_handle).await {
+                if let Err(e) = HotkeyManager::setup(hotkey_manager, app_handle).await {
                     log::error!("Failed to setup hotkeys: {}", e);
                 }
             });
@@ -177,19 +298,50 @@ fn main() {
                 }
             });
 
-            // Hide main window on startup (run in background)
+            // Replay the offline sync queue in the background
+            let app_handle = app.handle();
+            let sync_client = api_client.clone();
+            tokio::spawn(async move {
+                ApiClient::run_sync_loop(app_handle, sync_client).await;
+            });
+
+            // Stream live backend events to the webview in the background
+            let app_handle = app.handle();
+            let stream_client = api_client.clone();
+            tokio::spawn(async move {
+                ApiClient::run_event_stream(app_handle, stream_client).await;
+            });
+
+            // Accept commands from the companion CLI binary over a loopback socket
+            let app_handle = app.handle();
+            let ipc_client = api_client.clone();
+            tokio::spawn(async move {
+                ipc::serve(app_handle, ipc_client).await;
+            });
+
+            // Restore the main window to where the user left it, including whether
+            // it was visible when the app last closed.
             if let Some(window) = app.get_window("main") {
-                let _ = window.hide();
+                window_state::restore_geometry(&window);
+                let was_visible = window_state::load_geometry("main").map(|g| g.visible).unwrap_or(false);
+                if was_visible {
+                    let _ = window.show();
+                } else {
+                    let _ = window.hide();
+                }
             }
 
             log::info!("Aether Desktop Application initialized successfully");
             Ok(())
         })
         .on_window_event(|event| {
+            window_state::handle_window_event(event.window(), event.event());
+
             match event.event() {
                 WindowEvent::CloseRequested { api, .. } => {
                     // Hide window instead of closing when user clicks X
                     event.window().hide().unwrap();
+                    TrayManager::set_show_hide_label(&event.window().app_handle(), false);
                     api.prevent_close();
                 }
                 _ => {}
@@ -205,8 +357,63 @@ fn main() {
             show_main_window,
             hide_main_window,
             toggle_autostart,
-            is_autostart_enabled
+            is_autostart_enabled,
+            get_hotkeys,
+            set_hotkey,
+            get_pending_sync_count,
+            force_sync,
+            get_settings,
+            save_settings,
+            set_locale
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn extracts_capture_arg() {
+        assert_eq!(
+            extract_capture_arg(&argv(&["aether", "--capture", "buy milk"])),
+            Some("buy milk".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_capture_arg_absent_without_flag() {
+        assert_eq!(extract_capture_arg(&argv(&["aether"])), None);
+    }
+
+    #[test]
+    fn extract_capture_arg_absent_when_flag_is_last() {
+        assert_eq!(extract_capture_arg(&argv(&["aether", "--capture"])), None);
+    }
+
+    #[test]
+    fn extracts_route_arg_flag() {
+        assert_eq!(
+            extract_route_arg(&argv(&["aether", "--route", "dashboard"])),
+            Some("dashboard".to_string())
+        );
+    }
+
+    #[test]
+    fn extracts_route_arg_from_deep_link() {
+        assert_eq!(
+            extract_route_arg(&argv(&["aether", "aether://settings/"])),
+            Some("settings".to_string())
+        );
+    }
+
+    #[test]
+    fn extract_route_arg_absent_without_flag_or_link() {
+        assert_eq!(extract_route_arg(&argv(&["aether", "--capture", "note"])), None);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,212 @@
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+use sqlx::sqlite::{SqliteConnectOptions, SqlitePool, SqlitePoolOptions};
+use sqlx::Row;
+use std::path::PathBuf;
+use tokio::sync::OnceCell;
+
+/// Base delay (seconds) before the first retry of a failed sync entry; doubles per attempt.
+const BACKOFF_BASE_SECS: i64 = 30;
+/// Upper bound (seconds) so a long-queued entry doesn't wait forever between retries.
+const BACKOFF_MAX_SECS: i64 = 60 * 60;
+
+static QUEUE: OnceCell<SyncQueue> = OnceCell::const_new();
+
+/// Returns the process-wide offline sync queue, opening and migrating the
+/// SQLite database on first use.
+pub async fn queue() -> &'static SyncQueue {
+    QUEUE
+        .get_or_init(|| async {
+            SyncQueue::open()
+                .await
+                .expect("Failed to open offline sync queue database")
+        })
+        .await
+}
+
+#[derive(Debug, Clone)]
+pub struct PendingRequest {
+    pub id: i64,
+    pub endpoint: String,
+    pub method: String,
+    pub payload_json: String,
+    pub attempts: i64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PendingRequest {
+    /// Whether enough time has passed since creation for this attempt count's backoff window.
+    fn is_due(&self) -> bool {
+        let multiplier = 1i64.checked_shl(self.attempts.clamp(0, 16) as u32).unwrap_or(i64::MAX);
+        let backoff_secs = BACKOFF_BASE_SECS.saturating_mul(multiplier).min(BACKOFF_MAX_SECS);
+
+        Utc::now() >= self.created_at + chrono::Duration::seconds(backoff_secs)
+    }
+}
+
+/// Durable queue of mutations that failed to reach the Aether backend, backed
+/// by SQLite so pending work survives app restarts and can be replayed.
+pub struct SyncQueue {
+    pool: SqlitePool,
+}
+
+impl SyncQueue {
+    async fn open() -> Result<Self, Box<dyn std::error::Error>> {
+        let db_path = Self::db_path();
+        if let Some(parent) = db_path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+
+        let options = SqliteConnectOptions::new()
+            .filename(&db_path)
+            .create_if_missing(true);
+
+        let pool = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect_with(options)
+            .await?;
+
+        sqlx::query(
+            "CREATE TABLE IF NOT EXISTS pending_sync (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                endpoint TEXT NOT NULL,
+                method TEXT NOT NULL,
+                payload_json TEXT NOT NULL,
+                created_at TEXT NOT NULL,
+                attempts INTEGER NOT NULL DEFAULT 0,
+                synced_at TEXT
+            )",
+        )
+        .execute(&pool)
+        .await?;
+
+        Ok(Self { pool })
+    }
+
+    fn db_path() -> PathBuf {
+        dirs::data_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("aether")
+            .join("sync_queue.db")
+    }
+
+    pub async fn enqueue(
+        &self,
+        endpoint: &str,
+        method: &str,
+        payload: &Value,
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query(
+            "INSERT INTO pending_sync (endpoint, method, payload_json, created_at, attempts)
+             VALUES (?, ?, ?, ?, 0)",
+        )
+        .bind(endpoint)
+        .bind(method)
+        .bind(payload.to_string())
+        .bind(Utc::now().to_rfc3339())
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn pending_count(&self) -> Result<i64, Box<dyn std::error::Error>> {
+        let row = sqlx::query("SELECT COUNT(*) as count FROM pending_sync WHERE synced_at IS NULL")
+            .fetch_one(&self.pool)
+            .await?;
+        Ok(row.get::<i64, _>("count"))
+    }
+
+    /// Entries still pending, oldest first, regardless of backoff state.
+    async fn all_pending(&self) -> Result<Vec<PendingRequest>, Box<dyn std::error::Error>> {
+        let rows = sqlx::query(
+            "SELECT id, endpoint, method, payload_json, attempts, created_at
+             FROM pending_sync WHERE synced_at IS NULL ORDER BY created_at ASC",
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let created_at: String = row.get("created_at");
+                PendingRequest {
+                    id: row.get("id"),
+                    endpoint: row.get("endpoint"),
+                    method: row.get("method"),
+                    payload_json: row.get("payload_json"),
+                    attempts: row.get("attempts"),
+                    created_at: DateTime::parse_from_rfc3339(&created_at)
+                        .map(|dt| dt.with_timezone(&Utc))
+                        .unwrap_or_else(|_| Utc::now()),
+                }
+            })
+            .collect())
+    }
+
+    /// Entries eligible for a retry right now, taking each entry's backoff into account.
+    pub async fn due_entries(&self) -> Result<Vec<PendingRequest>, Box<dyn std::error::Error>> {
+        Ok(self
+            .all_pending()
+            .await?
+            .into_iter()
+            .filter(PendingRequest::is_due)
+            .collect())
+    }
+
+    pub async fn mark_synced(&self, id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query("UPDATE pending_sync SET synced_at = ? WHERE id = ?")
+            .bind(Utc::now().to_rfc3339())
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+
+    pub async fn record_attempt(&self, id: i64) -> Result<(), Box<dyn std::error::Error>> {
+        sqlx::query("UPDATE pending_sync SET attempts = attempts + 1 WHERE id = ?")
+            .bind(id)
+            .execute(&self.pool)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request_created(attempts: i64, age_secs: i64) -> PendingRequest {
+        PendingRequest {
+            id: 1,
+            endpoint: "/api/v1/ideas".to_string(),
+            method: "POST".to_string(),
+            payload_json: "{}".to_string(),
+            attempts,
+            created_at: Utc::now() - chrono::Duration::seconds(age_secs),
+        }
+    }
+
+    #[test]
+    fn not_due_before_base_backoff_elapses() {
+        assert!(!request_created(0, BACKOFF_BASE_SECS - 1).is_due());
+    }
+
+    #[test]
+    fn due_once_base_backoff_elapses() {
+        assert!(request_created(0, BACKOFF_BASE_SECS + 1).is_due());
+    }
+
+    #[test]
+    fn backoff_doubles_per_attempt() {
+        // After one failed attempt the window is 2x the base; not due right at the base.
+        assert!(!request_created(1, BACKOFF_BASE_SECS + 1).is_due());
+        assert!(request_created(1, BACKOFF_BASE_SECS * 2 + 1).is_due());
+    }
+
+    #[test]
+    fn backoff_is_capped_at_max_even_with_many_attempts() {
+        assert!(!request_created(63, BACKOFF_MAX_SECS - 1).is_due());
+        assert!(request_created(63, BACKOFF_MAX_SECS + 1).is_due());
+    }
+}
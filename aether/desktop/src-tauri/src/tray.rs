@@ -1,43 +1,165 @@
-use tauri::{AppHandle, Manager, SystemTrayEvent};
+use crate::i18n;
+use fluent_templates::LanguageIdentifier;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, CustomMenuItem, Manager, SystemTrayEvent, SystemTrayMenu, SystemTrayMenuItem};
+use tauri_plugin_positioner::{Position, WindowExt};
 
-pub struct TrayManager;
+pub type SharedTrayManager = Arc<Mutex<TrayManager>>;
+
+/// Checked state for the tray's toggle menu entries, persisted so they survive restarts.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default)]
+struct TrayToggles {
+    start_at_login: bool,
+    pause_capture: bool,
+    always_on_top: bool,
+}
+
+impl TrayToggles {
+    fn config_path() -> PathBuf {
+        dirs::config_dir()
+            .unwrap_or_else(|| PathBuf::from("."))
+            .join("aether")
+            .join("tray_toggles.json")
+    }
+
+    fn load() -> Self {
+        match std::fs::read_to_string(Self::config_path()) {
+            Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+            Err(_) => Self::default(),
+        }
+    }
+
+    fn save(&self) -> Result<(), Box<dyn std::error::Error>> {
+        let path = Self::config_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+pub struct TrayManager {
+    toggles: TrayToggles,
+}
 
 impl TrayManager {
-    pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent) {
+    pub fn new() -> Self {
+        Self {
+            toggles: TrayToggles::load(),
+        }
+    }
+
+    /// Builds the tray menu with titles resolved against `locale` and checkmarks
+    /// reflecting `toggles`.
+    fn build_menu(locale: &LanguageIdentifier, toggles: &TrayToggles) -> SystemTrayMenu {
+        SystemTrayMenu::new()
+            .add_item(CustomMenuItem::new("show".to_string(), i18n::text(locale, "show")))
+            .add_item(CustomMenuItem::new("capture".to_string(), i18n::text(locale, "capture")))
+            .add_native_item(SystemTrayMenuItem::Separator)
+            .add_item(CustomMenuItem::new("dashboard".to_string(), i18n::text(locale, "dashboard")))
+            .add_item(CustomMenuItem::new("settings".to_string(), i18n::text(locale, "settings")))
+            .add_native_item(SystemTrayMenuItem::Separator)
+            .add_item(Self::checkable_item("start_at_login", i18n::text(locale, "start-at-login"), toggles.start_at_login))
+            .add_item(Self::checkable_item("pause_capture", i18n::text(locale, "pause-capture"), toggles.pause_capture))
+            .add_item(Self::checkable_item("always_on_top", i18n::text(locale, "always-on-top"), toggles.always_on_top))
+            .add_native_item(SystemTrayMenuItem::Separator)
+            .add_item(CustomMenuItem::new("quit".to_string(), i18n::text(locale, "quit")))
+    }
+
+    fn checkable_item(id: &str, title: String, checked: bool) -> CustomMenuItem {
+        let item = CustomMenuItem::new(id.to_string(), title);
+        if checked {
+            item.selected()
+        } else {
+            item
+        }
+    }
+
+    /// Builds the initial tray menu for app startup, before any managed state exists.
+    pub fn initial_menu(locale: &LanguageIdentifier) -> SystemTrayMenu {
+        Self::build_menu(locale, &TrayToggles::load())
+    }
+
+    /// Re-applies persisted toggle state on startup: mirrors "Pause capture" into the
+    /// capture gate, "Always on top" onto the main window, and "Start at login" onto the
+    /// OS autostart registration, since all three live outside `TrayManager` itself and
+    /// don't survive a restart on their own.
+    pub fn apply_persisted_toggles(app: &AppHandle, state: &SharedTrayManager) {
+        let toggles = match state.lock() {
+            Ok(manager) => manager.toggles,
+            Err(_) => return,
+        };
+
+        crate::api::set_capture_paused(toggles.pause_capture);
+        if toggles.always_on_top {
+            if let Some(window) = app.get_window("main") {
+                let _ = window.set_always_on_top(true);
+            }
+        }
+
+        let start_at_login = toggles.start_at_login;
+        tokio::spawn(async move {
+            if let Err(e) = crate::autostart::AutoStartManager::new()
+                .toggle_autostart(start_at_login)
+                .await
+            {
+                log::error!("Failed to reconcile autostart with persisted toggle state: {}", e);
+            }
+        });
+    }
+
+    /// Rebuilds the tray menu from `state`'s current toggles and the active locale.
+    pub fn refresh_menu(app: &AppHandle, state: &SharedTrayManager) {
+        let toggles = match state.lock() {
+            Ok(manager) => manager.toggles,
+            Err(_) => TrayToggles::default(),
+        };
+        let locale = i18n::current_locale();
+        let _ = app.tray_handle().set_menu(Self::build_menu(&locale, &toggles));
+
+        // `build_menu` always renders "show" with its static title; `set_menu` just
+        // clobbered whatever live Show/Hide label chunk1-1's toggle tracked, so restore it.
+        let visible = app
+            .get_window("main")
+            .and_then(|window| window.is_visible().ok())
+            .unwrap_or(false);
+        Self::set_show_hide_label(app, visible);
+    }
+
+    /// Switches the active locale and rebuilds the tray menu with translated titles.
+    pub fn rebuild_menu(app: &AppHandle, state: &SharedTrayManager, locale: LanguageIdentifier) {
+        i18n::set_current_locale(locale);
+        Self::refresh_menu(app, state);
+    }
+
+    pub fn handle_tray_event(app: &AppHandle, event: SystemTrayEvent, state: &SharedTrayManager) {
+        tauri_plugin_positioner::on_tray_event(app, &event);
+
         match event {
-            SystemTrayEvent::LeftClick {
-                position: _,
-                size: _,
-                ..
-            } => {
+            SystemTrayEvent::LeftClick { .. } => {
                 log::info!("System tray left clicked");
-                Self::show_main_window(app);
+                Self::toggle_main_window(app);
             }
-            SystemTrayEvent::RightClick {
-                position: _,
-                size: _,
-                ..
-            } => {
+            SystemTrayEvent::RightClick { .. } => {
                 log::info!("System tray right clicked");
                 // Context menu is handled automatically by Tauri
             }
-            SystemTrayEvent::DoubleClick {
-                position: _,
-                size: _,
-                ..
-            } => {
+            SystemTrayEvent::DoubleClick { .. } => {
                 log::info!("System tray double clicked");
-                Self::show_main_window(app);
+                Self::toggle_main_window(app);
             }
             SystemTrayEvent::MenuItemClick { id, .. } => {
                 log::info!("System tray menu item clicked: {}", id);
-                Self::handle_menu_item_click(app, &id);
+                Self::handle_menu_item_click(app, &id, state);
             }
             _ => {}
         }
     }
 
-    fn handle_menu_item_click(app: &AppHandle, menu_id: &str) {
+    fn handle_menu_item_click(app: &AppHandle, menu_id: &str, state: &SharedTrayManager) {
         match menu_id {
             "show" => {
                 Self::show_main_window(app);
@@ -51,6 +173,15 @@ impl TrayManager {
             "settings" => {
                 Self::show_settings(app);
             }
+            "start_at_login" => {
+                Self::toggle_start_at_login(app, state);
+            }
+            "pause_capture" => {
+                Self::toggle_pause_capture(app, state);
+            }
+            "always_on_top" => {
+                Self::toggle_always_on_top(app, state);
+            }
             "quit" => {
                 log::info!("Quitting application from system tray");
                 app.exit(0);
@@ -61,24 +192,132 @@ impl TrayManager {
         }
     }
 
-    fn show_main_window(app: &AppHandle) {
+    /// Flips the toggle `name` in the shared state via `apply`, persists it, re-renders the menu's
+    /// checkmark, and emits `tray-toggle-changed` to the frontend. Returns the new value.
+    fn flip_toggle(
+        app: &AppHandle,
+        state: &SharedTrayManager,
+        name: &str,
+        apply: impl FnOnce(&mut TrayToggles) -> bool,
+    ) -> bool {
+        let enabled = {
+            let mut manager = state.lock().unwrap();
+            let enabled = apply(&mut manager.toggles);
+            if let Err(e) = manager.toggles.save() {
+                log::error!("Failed to persist tray toggles: {}", e);
+            }
+            enabled
+        };
+
+        Self::refresh_menu(app, state);
+        let _ = app.emit_all(
+            "tray-toggle-changed",
+            serde_json::json!({ "toggle": name, "enabled": enabled }),
+        );
+        enabled
+    }
+
+    fn toggle_start_at_login(app: &AppHandle, state: &SharedTrayManager) {
+        let enabled = Self::flip_toggle(app, state, "start_at_login", |toggles| {
+            toggles.start_at_login = !toggles.start_at_login;
+            toggles.start_at_login
+        });
+
+        tokio::spawn(async move {
+            if let Err(e) = crate::autostart::AutoStartManager::new().toggle_autostart(enabled).await {
+                log::error!("Failed to toggle autostart from tray: {}", e);
+            }
+        });
+    }
+
+    fn toggle_pause_capture(app: &AppHandle, state: &SharedTrayManager) {
+        let enabled = Self::flip_toggle(app, state, "pause_capture", |toggles| {
+            toggles.pause_capture = !toggles.pause_capture;
+            toggles.pause_capture
+        });
+
+        crate::api::set_capture_paused(enabled);
+    }
+
+    fn toggle_always_on_top(app: &AppHandle, state: &SharedTrayManager) {
+        let enabled = Self::flip_toggle(app, state, "always_on_top", |toggles| {
+            toggles.always_on_top = !toggles.always_on_top;
+            toggles.always_on_top
+        });
+
+        if let Some(window) = app.get_window("main") {
+            let _ = window.set_always_on_top(enabled);
+        }
+    }
+
+    /// Shows and focuses the main window, restoring its last known geometry. Used by the
+    /// tray's "Show" item, global hotkeys, and single-instance/deep-link relaunches.
+    pub fn show_main_window(app: &AppHandle) {
         if let Some(window) = app.get_window("main") {
             let _ = window.show();
             let _ = window.set_focus();
             let _ = window.unminimize();
-            let _ = window.center();
+            crate::window_state::restore_geometry(&window);
             log::info!("Main window shown and focused");
+            Self::set_show_hide_label(app, true);
         } else {
             log::error!("Main window not found");
         }
     }
 
+    /// Routes to a named view (`"dashboard"`, `"settings"`, `"capture"`), falling back to
+    /// just showing the main window for an unrecognized or absent route. Used to funnel
+    /// a second-instance launch or `aether://` deep link straight to the right screen.
+    pub fn navigate(app: &AppHandle, route: &str) {
+        match route {
+            "dashboard" => Self::show_dashboard(app),
+            "settings" => Self::show_settings(app),
+            "capture" => Self::show_quick_capture(app),
+            other => {
+                log::warn!("Unknown navigation route '{}', showing main window instead", other);
+                Self::show_main_window(app);
+            }
+        }
+    }
+
+    /// Shows and focuses the main window if it's hidden or unfocused, otherwise hides it.
+    pub fn toggle_main_window(app: &AppHandle) {
+        if let Some(window) = app.get_window("main") {
+            let visible = window.is_visible().unwrap_or(false);
+            let focused = window.is_focused().unwrap_or(false);
+
+            if visible && focused {
+                let _ = window.hide();
+                log::info!("Main window hidden via tray toggle");
+                Self::set_show_hide_label(app, false);
+            } else {
+                let _ = window.show();
+                let _ = window.set_focus();
+                let _ = window.unminimize();
+                crate::window_state::restore_geometry(&window);
+                log::info!("Main window shown via tray toggle");
+                Self::set_show_hide_label(app, true);
+            }
+        }
+    }
+
+    /// Keeps the tray menu's "show" item in sync with the main window's visibility.
+    pub fn set_show_hide_label(app: &AppHandle, visible: bool) {
+        let locale = i18n::current_locale();
+        let id = if visible { "hide" } else { "show" };
+        let _ = app.tray_handle().get_item("show").set_title(i18n::text(&locale, id));
+    }
+
+    /// Shows the quick-capture popup anchored next to the tray icon, like a menubar app.
     fn show_quick_capture(app: &AppHandle) {
         // Try to show existing quick capture window or create new one
         if let Some(window) = app.get_window("quick-capture") {
             let _ = window.show();
             let _ = window.set_focus();
-            let _ = window.center();
+            // Restore the saved size before anchoring, so a resize persists across
+            // shows without the saved position fighting the tray anchor.
+            crate::window_state::restore_size(&window);
+            let _ = window.move_window(Position::TrayCenter);
         } else {
             // Create quick capture window
             let window_result = tauri::WindowBuilder::new(
@@ -92,11 +331,14 @@ impl TrayManager {
             .resizable(true)
             .decorations(true)
             .always_on_top(true)
-            .center()
             .build();
 
             match window_result {
                 Ok(window) => {
+                    // Restore the saved size before anchoring, same reasoning as the
+                    // existing-window path above: position always follows the tray icon.
+                    crate::window_state::restore_size(&window);
+                    let _ = window.move_window(Position::TrayCenter);
                     let _ = window.show();
                     let _ = window.set_focus();
                     log::info!("Quick capture window created and shown");
@@ -114,7 +356,7 @@ impl TrayManager {
             let _ = window.show();
             let _ = window.set_focus();
             let _ = window.unminimize();
-            
+
             // Emit event to frontend to navigate to dashboard
             let _ = window.emit("navigate", "dashboard");
             log::info!("Navigated to dashboard");
@@ -127,10 +369,10 @@ impl TrayManager {
             let _ = window.show();
             let _ = window.set_focus();
             let _ = window.unminimize();
-            
+
             // Emit event to frontend to navigate to settings
             let _ = window.emit("navigate", "settings");
             log::info!("Navigated to settings");
         }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,103 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use tauri::{PhysicalPosition, PhysicalSize, Window, WindowEvent};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub visible: bool,
+}
+
+type WindowStates = HashMap<String, WindowGeometry>;
+
+fn state_path() -> PathBuf {
+    dirs::data_dir()
+        .unwrap_or_else(|| PathBuf::from("."))
+        .join("aether")
+        .join("window_state.json")
+}
+
+fn load_all() -> WindowStates {
+    let path = state_path();
+    match std::fs::read_to_string(&path) {
+        Ok(contents) => serde_json::from_str(&contents).unwrap_or_default(),
+        Err(_) => WindowStates::default(),
+    }
+}
+
+fn save_all(states: &WindowStates) -> Result<(), Box<dyn std::error::Error>> {
+    let path = state_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(states)?)?;
+    Ok(())
+}
+
+pub fn load_geometry(label: &str) -> Option<WindowGeometry> {
+    load_all().get(label).cloned()
+}
+
+fn save_geometry(label: &str, geometry: WindowGeometry) {
+    let mut states = load_all();
+    states.insert(label.to_string(), geometry);
+    if let Err(e) = save_all(&states) {
+        log::error!("Failed to save window state for '{}': {}", label, e);
+    }
+}
+
+/// Persists a window's geometry/visibility whenever it moves, resizes, or is closed.
+pub fn handle_window_event(window: &Window, event: &WindowEvent) {
+    match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) => {
+            persist_current(window, window.is_visible().unwrap_or(true));
+        }
+        WindowEvent::CloseRequested { .. } => {
+            persist_current(window, false);
+        }
+        _ => {}
+    }
+}
+
+fn persist_current(window: &Window, visible: bool) {
+    let position = window.outer_position().unwrap_or(PhysicalPosition::new(0, 0));
+    let size = window.outer_size().unwrap_or(PhysicalSize::new(800, 600));
+    let maximized = window.is_maximized().unwrap_or(false);
+
+    save_geometry(
+        window.label(),
+        WindowGeometry {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+            maximized,
+            visible,
+        },
+    );
+}
+
+/// Restores a window's last known position, size, and maximized state, if any was saved.
+pub fn restore_geometry(window: &Window) {
+    if let Some(geometry) = load_geometry(window.label()) {
+        let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+        let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+        if geometry.maximized {
+            let _ = window.maximize();
+        }
+    }
+}
+
+/// Restores only a window's last known size, leaving its position alone. Used by windows
+/// that re-anchor their position on every show (e.g. quick-capture next to the tray icon),
+/// where restoring the saved position would fight the anchor instead of complementing it.
+pub fn restore_size(window: &Window) {
+    if let Some(geometry) = load_geometry(window.label()) {
+        let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+    }
+}